@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// Errors surfaced by the Huffman encoder/decoder, used in place of the
+/// `unwrap`/`expect`/`panic!` the implementation used to fall back to.
+#[derive(Debug)]
+pub enum EncoderError {
+    Io(std::io::Error),
+    EmptyInput,
+    UnknownSymbol,
+    MalformedHeader,
+    TruncatedStream,
+}
+
+impl fmt::Display for EncoderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncoderError::Io(e) => write!(f, "I/O error: {e}"),
+            EncoderError::EmptyInput => write!(f, "input contains no symbols to encode"),
+            EncoderError::UnknownSymbol => {
+                write!(f, "encountered a symbol with no assigned code")
+            }
+            EncoderError::MalformedHeader => write!(f, "compressed file header is malformed"),
+            EncoderError::TruncatedStream => {
+                write!(f, "compressed file ended before it was fully read")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncoderError {}
+
+impl From<std::io::Error> for EncoderError {
+    fn from(e: std::io::Error) -> Self {
+        EncoderError::Io(e)
+    }
+}
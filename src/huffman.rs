@@ -1,163 +1,931 @@
 pub mod huffman {
+    use crate::error::EncoderError;
     use rust_ds::heap::heap::BinaryHeap;
     use std::cmp::Ordering;
     use std::collections::HashMap;
     use std::fmt;
     use std::fs;
+    use std::fs::File;
+    use std::hash::Hash;
+    use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
     use std::path::Path;
 
-    #[derive(Clone)]
-    pub struct HuffmanNode {
-        ch: Option<char>,
-        f: u32,
-        l: Option<Box<HuffmanNode>>,
-        r: Option<Box<HuffmanNode>>,
+    /// A node in the Huffman tree arena: `l`/`r` are indices into the same
+    /// `Vec<ArenaNode<S>>` rather than `Box`-linked children, so building
+    /// the tree pushes plain values instead of allocating one heap cell per
+    /// node.
+    struct ArenaNode<S> {
+        sym: Option<S>,
+        l: Option<usize>,
+        r: Option<usize>,
     }
 
-    impl HuffmanNode {
-        pub fn single(ch: Option<char>, f: u32) -> Self {
-            HuffmanNode {
-                f,
-                ch,
-                l: None,
-                r: None,
-            }
-        }
+    /// An entry in the build-time priority queue: just enough to order
+    /// candidates by weight and say which arena slot they refer to, so the
+    /// heap never has to clone a whole node.
+    #[derive(Clone, Copy)]
+    struct HeapEntry {
+        f: u32,
+        idx: usize,
     }
 
-    impl PartialOrd for HuffmanNode {
+    impl PartialOrd for HeapEntry {
         fn partial_cmp(&self, o: &Self) -> Option<Ordering> {
             Some(self.cmp(o))
         }
     }
 
-    impl Ord for HuffmanNode {
+    impl Ord for HeapEntry {
         fn cmp(&self, o: &Self) -> Ordering {
             self.f.cmp(&o.f)
         }
     }
 
-    impl PartialEq for HuffmanNode {
+    impl PartialEq for HeapEntry {
         fn eq(&self, o: &Self) -> bool {
-            self.f == o.f && self.ch == o.ch && self.l == o.l && self.r == o.r
+            self.f == o.f
         }
     }
 
-    impl Eq for HuffmanNode {}
+    impl Eq for HeapEntry {}
+
+    /// Builds a Huffman tree as a flat arena sized up front to the known
+    /// maximum of `2 * distinct_symbols - 1` nodes: push a leaf per symbol,
+    /// then repeatedly pop the two smallest-weight indices off the heap and
+    /// push a new internal node that references them. Returns the arena and
+    /// the index of its root, or `None` for empty input.
+    fn build_arena<S: Copy + Eq + Hash + Ord>(
+        frequencies: &HashMap<S, u32>,
+    ) -> Option<(Vec<ArenaNode<S>>, usize)> {
+        // HashMap iteration order is unspecified, so without a fixed
+        // insertion order two tied-frequency symbols could end up at
+        // different depths on different runs of the same input, making the
+        // assigned code lengths - and thus the compressed output - diverge
+        // from one encode to the next.
+        let mut sorted_frequencies: Vec<(&S, &u32)> = frequencies.iter().collect();
+        sorted_frequencies.sort_by_key(|(s, _)| **s);
+
+        if sorted_frequencies.is_empty() {
+            return None;
+        }
 
-    fn build_huffman_tree(content: &String) -> BinaryHeap<HuffmanNode> {
-        // Logic:
-        // (i) Count char frequencies and place these in a priority queue (BinaryHeap).
-        // (ii) Construct a Huffman tree:
-        // Properties:
-        // Minimal external path weight - meaning smallest sum of paths for leaves.
-        // This can be constructed by adding greedily the smallest node to a tree so that the leaf distance is minimized.
+        let mut arena: Vec<ArenaNode<S>> = Vec::with_capacity(2 * sorted_frequencies.len() - 1);
+        let mut heap: BinaryHeap<HeapEntry> = BinaryHeap::default();
 
-        let mut pq = content
-            .chars()
-            .fold(HashMap::new(), |mut acc, c| {
-                let counter = acc.entry(c).or_insert(0 as u16);
-                *counter += 1;
-                acc
-            })
-            .iter()
-            .fold(BinaryHeap::default(), |mut pq, (c, f)| {
-                pq.insert(HuffmanNode::single(Some(*c), *f as u32));
-                pq
+        for (s, f) in sorted_frequencies {
+            let idx = arena.len();
+            arena.push(ArenaNode {
+                sym: Some(*s),
+                l: None,
+                r: None,
             });
+            heap.insert(HeapEntry { f: *f, idx });
+        }
 
         // while left and right still in queue,
-        while pq.size() > 1 {
-            let l = pq.pop().unwrap();
-            let r = pq.pop().unwrap();
-
-            let p = HuffmanNode {
-                f: l.f + r.f,
-                ch: None, // dummy since won't be used
-                l: Some(Box::new(l)),
-                r: Some(Box::new(r)),
-            };
+        while heap.size() > 1 {
+            let l = heap.pop().unwrap();
+            let r = heap.pop().unwrap();
+
+            let idx = arena.len();
+            arena.push(ArenaNode {
+                sym: None, // dummy since won't be used
+                l: Some(l.idx),
+                r: Some(r.idx),
+            });
 
-            pq.insert(p);
+            heap.insert(HeapEntry { f: l.f + r.f, idx });
         }
 
-        pq
+        let root = heap.peek().unwrap().idx;
+        Some((arena, root))
     }
 
-    pub fn build_huffman_encoding(
-        content: &String,
-    ) -> Option<(HashMap<char, String>, BinaryHeap<HuffmanNode>)> {
-        // Idea:
-        // Recursively walk down the Huffman tree, add to encoding if leaf node.
-        let mut pq = build_huffman_tree(content);
+    /// A canonical Huffman code: `val` holds the `num_bits` code bits,
+    /// right-aligned (i.e. as an ordinary unsigned integer).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Encoding {
+        pub val: u64,
+        pub num_bits: u8,
+    }
 
-        if pq.size() == 0 {
-            return None;
+    fn char_frequencies(content: &String) -> HashMap<char, u32> {
+        content.chars().fold(HashMap::new(), |mut acc, c| {
+            let counter = acc.entry(c).or_insert(0 as u32);
+            *counter += 1;
+            acc
+        })
+    }
+
+    /// Counts byte frequencies using a fixed 256-entry table so every
+    /// possible byte value, not just valid UTF-8 text, can be tallied.
+    fn byte_frequencies(content: &[u8]) -> HashMap<u8, u32> {
+        let mut counts = [0u32; 256];
+
+        for &b in content {
+            counts[b as usize] += 1;
         }
 
-        let pq_clone = pq.clone();
+        counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &f)| f > 0)
+            .map(|(b, &f)| (b as u8, f))
+            .collect()
+    }
 
-        let root = Box::new(pq.pop().unwrap());
+    /// Computes each symbol's code *length* (its depth in the Huffman tree),
+    /// which is all the canonical-code assignment below needs. Walks the
+    /// arena with an explicit stack rather than recursion, so there is no
+    /// call-stack depth tied to input size. Returns `None` for empty input.
+    fn code_lengths<S: Copy + Eq + Hash + Ord>(
+        frequencies: &HashMap<S, u32>,
+    ) -> Option<HashMap<S, u8>> {
+        let (arena, root) = build_arena(frequencies)?;
+        let mut lengths = HashMap::new();
+
+        // A single distinct symbol collapses the tree to a lone leaf with no
+        // internal parent, i.e. depth 0. Force it to a 1-bit code instead so
+        // every symbol still costs at least one bit.
+        if let Some(sym) = arena[root].sym {
+            lengths.insert(sym, 1);
+            return Some(lengths);
+        }
 
-        fn recursive_encoding(n: &Box<HuffmanNode>, e: &mut HashMap<char, String>, s: String) {
-            if let Some(ch) = n.ch {
-                e.insert(ch, s);
+        let mut stack = vec![(root, 0u8)];
+        while let Some((idx, depth)) = stack.pop() {
+            let node = &arena[idx];
+            if let Some(sym) = node.sym {
+                lengths.insert(sym, depth);
             } else {
-                if let Some(ref l) = n.l {
-                    recursive_encoding(l, e, s.clone() + "0");
+                if let Some(l) = node.l {
+                    stack.push((l, depth + 1));
+                }
+                if let Some(r) = node.r {
+                    stack.push((r, depth + 1));
                 }
-                if let Some(ref r) = n.r {
-                    recursive_encoding(r, e, s.clone() + "1");
+            }
+        }
+
+        Some(lengths)
+    }
+
+    /// Assigns canonical Huffman codes from a code-length table: sort
+    /// symbols by `(length, symbol)`, start the first code at 0, and for
+    /// each subsequent symbol compute `code = (prev_code + 1) << (len - prev_len)`.
+    /// Rebuilding this from just the lengths (no tree, no frequencies) is
+    /// what lets the compressed header shrink to one length per symbol.
+    ///
+    /// `lengths` may come straight from a decoded file header, so every
+    /// length is validated rather than trusted: a length of 0, or a gap
+    /// between consecutive lengths of 64 or more, would otherwise overflow
+    /// the `u64` shift below. Either is reported as `MalformedHeader`
+    /// instead of panicking.
+    fn assign_canonical_codes<S: Copy + Eq + Hash + Ord>(
+        lengths: &HashMap<S, u8>,
+    ) -> Result<HashMap<S, Encoding>, EncoderError> {
+        let mut symbols: Vec<(S, u8)> = lengths.iter().map(|(s, l)| (*s, *l)).collect();
+        symbols.sort_by_key(|(s, l)| (*l, *s));
+
+        if symbols.iter().any(|(_, l)| *l == 0) {
+            return Err(EncoderError::MalformedHeader);
+        }
+
+        let mut encodings = HashMap::new();
+        let mut code: u64 = 0;
+        let mut prev_len: u8 = 0;
+
+        for (i, (sym, len)) in symbols.iter().enumerate() {
+            if i > 0 {
+                code = code
+                    .checked_add(1)
+                    .and_then(|c| c.checked_shl((len - prev_len) as u32))
+                    .ok_or(EncoderError::MalformedHeader)?;
+            }
+
+            encodings.insert(
+                *sym,
+                Encoding {
+                    val: code,
+                    num_bits: *len,
+                },
+            );
+            prev_len = *len;
+        }
+
+        Ok(encodings)
+    }
+
+    pub fn build_canonical_encoding(
+        content: &String,
+    ) -> Result<HashMap<char, Encoding>, EncoderError> {
+        let lengths = code_lengths(&char_frequencies(content)).ok_or(EncoderError::EmptyInput)?;
+        assign_canonical_codes(&lengths)
+    }
+
+    /// Byte-mode counterpart of `build_canonical_encoding`, for arbitrary
+    /// binary content rather than valid UTF-8 text.
+    pub fn build_canonical_byte_encoding(
+        content: &[u8],
+    ) -> Result<HashMap<u8, Encoding>, EncoderError> {
+        let lengths = code_lengths(&byte_frequencies(content)).ok_or(EncoderError::EmptyInput)?;
+        assign_canonical_codes(&lengths)
+    }
+
+    /// Writes bits to an underlying `Write`, most-significant-bit first,
+    /// holding only a single partial byte in memory at a time instead of
+    /// materializing the whole bitstream before packing it.
+    struct BitWriter<W: Write> {
+        writer: W,
+        buf: u8,
+        nbits: u8,
+    }
+
+    impl<W: Write> BitWriter<W> {
+        fn new(writer: W) -> Self {
+            BitWriter {
+                writer,
+                buf: 0,
+                nbits: 0,
+            }
+        }
+
+        fn write_bits(&mut self, val: u64, num_bits: u8) -> io::Result<()> {
+            for i in (0..num_bits).rev() {
+                let bit = ((val >> i) & 1) as u8;
+                self.buf = (self.buf << 1) | bit;
+                self.nbits += 1;
+                if self.nbits == 8 {
+                    self.writer.write_all(&[self.buf])?;
+                    self.buf = 0;
+                    self.nbits = 0;
                 }
             }
+            Ok(())
+        }
+
+        /// Flushes the trailing partial byte, zero-padded, and returns the
+        /// wrapped writer along with how many padding bits were added.
+        fn finish(mut self) -> io::Result<(W, u8)> {
+            let padding = if self.nbits == 0 {
+                0
+            } else {
+                let padding = 8 - self.nbits;
+                self.buf <<= padding;
+                self.writer.write_all(&[self.buf])?;
+                padding
+            };
+            Ok((self.writer, padding))
+        }
+    }
+
+    /// Reads bits from an underlying `Read`, most-significant-bit first,
+    /// keeping one byte of lookahead so it can tell when the current byte is
+    /// the final one and stop before its padding bits.
+    struct BitReader<R: Read> {
+        reader: R,
+        cur: Option<u8>,
+        next: Option<u8>,
+        bit_pos: u8,
+    }
+
+    impl<R: Read> BitReader<R> {
+        fn new(mut reader: R) -> io::Result<Self> {
+            let cur = Self::read_one(&mut reader)?;
+            let next = if cur.is_some() {
+                Self::read_one(&mut reader)?
+            } else {
+                None
+            };
+            Ok(BitReader {
+                reader,
+                cur,
+                next,
+                bit_pos: 0,
+            })
+        }
+
+        fn read_one(reader: &mut R) -> io::Result<Option<u8>> {
+            let mut byte = [0u8; 1];
+            match reader.read(&mut byte)? {
+                0 => Ok(None),
+                _ => Ok(Some(byte[0])),
+            }
+        }
+
+        /// Returns the next data bit, or `None` once only the trailing
+        /// `padding` bits of the final byte (or end of stream) remain.
+        fn next_bit(&mut self, padding: u8) -> io::Result<Option<bool>> {
+            let Some(byte) = self.cur else {
+                return Ok(None);
+            };
+
+            let limit = if self.next.is_none() { 8 - padding } else { 8 };
+            if self.bit_pos >= limit {
+                return Ok(None);
+            }
+
+            let bit = (byte >> (7 - self.bit_pos)) & 1 == 1;
+            self.bit_pos += 1;
+
+            if self.bit_pos == 8 {
+                self.cur = self.next;
+                self.bit_pos = 0;
+                self.next = Self::read_one(&mut self.reader)?;
+            }
+
+            Ok(Some(bit))
+        }
+    }
+
+    /// Total number of payload bits a symbol stream will pack down to, given
+    /// how often each symbol occurs and how long its canonical code is -
+    /// enough to compute the padding byte without walking the symbols twice.
+    fn total_symbol_bits<S: Eq + Hash>(
+        frequencies: &HashMap<S, u32>,
+        lengths: &HashMap<S, u8>,
+    ) -> u64 {
+        frequencies
+            .iter()
+            .map(|(s, f)| *f as u64 * lengths[s] as u64)
+            .sum()
+    }
+
+    /// Streams symbols straight into packed bytes via a `BitWriter`, so the
+    /// compressed payload is never held as an intermediate string of '0'/'1'
+    /// characters.
+    fn write_symbols_packed<S: Copy + Eq + Hash, W: Write>(
+        symbols: impl Iterator<Item = S>,
+        encodings: &HashMap<S, Encoding>,
+        writer: W,
+    ) -> Result<(), EncoderError> {
+        let mut bit_writer = BitWriter::new(writer);
+        for s in symbols {
+            let enc = encodings.get(&s).ok_or(EncoderError::UnknownSymbol)?;
+            bit_writer.write_bits(enc.val, enc.num_bits)?;
+        }
+        bit_writer.finish()?;
+        Ok(())
+    }
+
+    /// Decodes symbols packed by `write_symbols_packed`. Canonical
+    /// codes are prefix-free, so reading one bit at a time and checking the
+    /// accumulated `(num_bits, val)` against the encoding table is enough -
+    /// there is never more than one match at a given length.
+    fn decode_canonical<S: Copy + Eq + Hash>(
+        encodings: &HashMap<S, Encoding>,
+        payload: &[u8],
+        padding: usize,
+    ) -> Result<Vec<S>, EncoderError> {
+        let mut by_code: HashMap<(u8, u64), S> = HashMap::new();
+        for (sym, enc) in encodings {
+            by_code.insert((enc.num_bits, enc.val), *sym);
+        }
+
+        let total_bits = payload
+            .len()
+            .checked_mul(8)
+            .and_then(|bits| bits.checked_sub(padding))
+            .ok_or(EncoderError::MalformedHeader)?;
+
+        let mut decoded = Vec::new();
+        let mut code: u64 = 0;
+        let mut num_bits: u8 = 0;
+
+        for i in 0..total_bits {
+            let byte = payload[i / 8];
+            let bit = ((byte >> (7 - i % 8)) & 1) as u64;
+            code = (code << 1) | bit;
+            num_bits += 1;
+
+            if let Some(sym) = by_code.get(&(num_bits, code)) {
+                decoded.push(*sym);
+                code = 0;
+                num_bits = 0;
+            }
+        }
+
+        // Leftover bits that never matched a code mean the stream was cut
+        // off mid-symbol rather than at a symbol boundary.
+        if num_bits != 0 {
+            return Err(EncoderError::TruncatedStream);
         }
 
-        let mut encodings: HashMap<char, String> = HashMap::new();
+        Ok(decoded)
+    }
+
+    /// Serializes a code-length table as: leading u32 count of distinct
+    /// symbols, followed by that many (u32 char, u8 length) pairs.
+    fn serialize_char_lengths(lengths: &HashMap<char, u8>) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend_from_slice(&(lengths.len() as u32).to_le_bytes());
 
-        recursive_encoding(&root, &mut encodings, "".to_string());
+        for (c, l) in lengths {
+            header.extend_from_slice(&(*c as u32).to_le_bytes());
+            header.push(*l);
+        }
 
-        Some((encodings, pq_clone))
+        header
     }
 
-    fn encode_string(input_content: &String) {
-        let mut bits: usize;
+    /// Inverts `serialize_char_lengths`, returning the table and the number
+    /// of bytes consumed from `bytes`.
+    fn deserialize_char_lengths(bytes: &[u8]) -> Result<(HashMap<char, u8>, usize), EncoderError> {
+        let count_bytes = bytes.get(0..4).ok_or(EncoderError::TruncatedStream)?;
+        let num_symbols = u32::from_le_bytes(count_bytes.try_into().unwrap()) as usize;
+        let mut offset = 4;
+        let mut lengths = HashMap::new();
+
+        for _ in 0..num_symbols {
+            let char_bytes = bytes
+                .get(offset..offset + 4)
+                .ok_or(EncoderError::TruncatedStream)?;
+            let ch = char::from_u32(u32::from_le_bytes(char_bytes.try_into().unwrap()))
+                .ok_or(EncoderError::MalformedHeader)?;
+            let l = *bytes.get(offset + 4).ok_or(EncoderError::TruncatedStream)?;
+            lengths.insert(ch, l);
+            offset += 5;
+        }
 
-        bits = input_content.len() * 8;
+        Ok((lengths, offset))
+    }
+
+    /// Byte-mode counterpart of `serialize_char_lengths`. Since the symbol
+    /// space is the fixed 256 byte values, a 256-bit presence bitmap plus one
+    /// length byte per present value is more compact than repeating the
+    /// symbol itself.
+    fn serialize_byte_lengths(lengths: &HashMap<u8, u8>) -> Vec<u8> {
+        let mut bitmap = [0u8; 32];
+        for &b in lengths.keys() {
+            bitmap[b as usize / 8] |= 1 << (7 - b as usize % 8);
+        }
 
+        let mut header = bitmap.to_vec();
+        for b in 0u16..256 {
+            if let Some(l) = lengths.get(&(b as u8)) {
+                header.push(*l);
+            }
+        }
+
+        header
+    }
+
+    /// Inverts `serialize_byte_lengths`.
+    fn deserialize_byte_lengths(bytes: &[u8]) -> Result<(HashMap<u8, u8>, usize), EncoderError> {
+        let bitmap = bytes.get(0..32).ok_or(EncoderError::TruncatedStream)?;
+        let mut offset = 32;
+        let mut lengths = HashMap::new();
+
+        for b in 0u16..256 {
+            let present = (bitmap[b as usize / 8] >> (7 - b as usize % 8)) & 1 == 1;
+            if present {
+                let l = *bytes.get(offset).ok_or(EncoderError::TruncatedStream)?;
+                lengths.insert(b as u8, l);
+                offset += 1;
+            }
+        }
+
+        Ok((lengths, offset))
+    }
+
+    /// Leading byte of every encoded file, identifying which code-length
+    /// table format the rest of the header uses.
+    const MODE_TEXT: u8 = 0;
+    const MODE_BYTES: u8 = 1;
+
+    fn encode_string(input_content: &String) -> Result<Vec<u8>, EncoderError> {
+        let bits = input_content.len() * 8;
         println!("Uncompressed data size: {bits} bits.");
 
         println!("Building Huffman encoding..");
-        let (encoding_map, huffman_tree) = build_huffman_encoding(&input_content).unwrap();
+        let mut output = vec![MODE_TEXT];
+
+        let frequencies = char_frequencies(input_content);
+        let lengths = match code_lengths(&frequencies) {
+            None => {
+                output.extend(serialize_char_lengths(&HashMap::new()));
+                output.push(0);
+                return Ok(output);
+            }
+            Some(l) => l,
+        };
+        let encodings = assign_canonical_codes(&lengths)?;
+        output.extend(serialize_char_lengths(&lengths));
 
-        let compressed_content = input_content.chars().fold(String::new(), |mut acc, c| {
-            acc.push_str(encoding_map.get(&c).unwrap());
-            acc
-        });
+        let total_bits = total_symbol_bits(&frequencies, &lengths);
+        println!("Compressed data size: {total_bits} bits.");
+        output.push(((8 - total_bits % 8) % 8) as u8);
 
-        bits = compressed_content.len();
-        println!("Compressed data size: {bits} bits.");
-        println!("Compressed data:\n{compressed_content}");
+        write_symbols_packed(input_content.chars(), &encodings, &mut output)?;
+
+        Ok(output)
+    }
+
+    /// Byte-mode counterpart of `encode_string`, for arbitrary binary
+    /// content rather than valid UTF-8 text.
+    fn encode_byte_content(input_content: &[u8]) -> Result<Vec<u8>, EncoderError> {
+        let bits = input_content.len() * 8;
+        println!("Uncompressed data size: {bits} bits.");
+
+        println!("Building Huffman encoding..");
+        let mut output = vec![MODE_BYTES];
+
+        let frequencies = byte_frequencies(input_content);
+        let lengths = match code_lengths(&frequencies) {
+            None => {
+                output.extend(serialize_byte_lengths(&HashMap::new()));
+                output.push(0);
+                return Ok(output);
+            }
+            Some(l) => l,
+        };
+        let encodings = assign_canonical_codes(&lengths)?;
+        output.extend(serialize_byte_lengths(&lengths));
+
+        let total_bits = total_symbol_bits(&frequencies, &lengths);
+        println!("Compressed data size: {total_bits} bits.");
+        output.push(((8 - total_bits % 8) % 8) as u8);
+
+        write_symbols_packed(input_content.iter().copied(), &encodings, &mut output)?;
+
+        Ok(output)
+    }
+
+    fn decode_text_content(content: &[u8]) -> Result<String, EncoderError> {
+        let (lengths, offset) = deserialize_char_lengths(content)?;
+
+        if lengths.is_empty() {
+            return Ok(String::new());
+        }
+
+        let encodings = assign_canonical_codes(&lengths)?;
+        let padding = *content.get(offset).ok_or(EncoderError::TruncatedStream)? as usize;
+        let payload = content
+            .get(offset + 1..)
+            .ok_or(EncoderError::TruncatedStream)?;
+
+        Ok(decode_canonical(&encodings, payload, padding)?
+            .into_iter()
+            .collect())
+    }
+
+    fn decode_byte_content(content: &[u8]) -> Result<Vec<u8>, EncoderError> {
+        let (lengths, offset) = deserialize_byte_lengths(content)?;
+
+        if lengths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let encodings = assign_canonical_codes(&lengths)?;
+        let padding = *content.get(offset).ok_or(EncoderError::TruncatedStream)? as usize;
+        let payload = content
+            .get(offset + 1..)
+            .ok_or(EncoderError::TruncatedStream)?;
+
+        decode_canonical(&encodings, payload, padding)
+    }
+
+    /// Counts byte frequencies over a `Read` source in fixed-size chunks, so
+    /// the whole input never has to be held in memory at once.
+    fn gather_byte_frequencies<R: Read>(reader: &mut R) -> Result<HashMap<u8, u32>, EncoderError> {
+        let mut counts = [0u32; 256];
+        let mut buf = [0u8; 8192];
+
+        loop {
+            let n = reader.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            for &b in &buf[..n] {
+                counts[b as usize] += 1;
+            }
+        }
+
+        Ok(counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &f)| f > 0)
+            .map(|(b, &f)| (b as u8, f))
+            .collect())
     }
 
-    pub fn encode(input_path: &String, output_path: &String) {
-        let input_content = read_file(input_path);
+    /// Streaming counterpart of `encode_byte_content`: makes a first pass
+    /// over `reader` to gather byte frequencies (and, from those, the exact
+    /// payload bit count), seeks back to the start, then streams the second
+    /// pass straight into packed bytes on `writer` through a `BitWriter`.
+    /// Neither the input nor the compressed payload is ever materialized in
+    /// full, so this scales to files much larger than available memory.
+    pub fn encode_byte_stream<R: Read + Seek, W: Write>(
+        mut reader: R,
+        mut writer: W,
+    ) -> Result<(), EncoderError> {
+        let frequencies = gather_byte_frequencies(&mut reader)?;
+        writer.write_all(&[MODE_BYTES])?;
+
+        let lengths = match code_lengths(&frequencies) {
+            None => {
+                writer.write_all(&serialize_byte_lengths(&HashMap::new()))?;
+                writer.write_all(&[0])?;
+                return Ok(());
+            }
+            Some(l) => l,
+        };
+        let encodings = assign_canonical_codes(&lengths)?;
+        writer.write_all(&serialize_byte_lengths(&lengths))?;
+
+        let total_bits = total_symbol_bits(&frequencies, &lengths);
+        writer.write_all(&[((8 - total_bits % 8) % 8) as u8])?;
+
+        reader.seek(SeekFrom::Start(0))?;
+        let mut bit_writer = BitWriter::new(writer);
+        let mut buf = [0u8; 8192];
+        loop {
+            let n = reader.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            for &b in &buf[..n] {
+                let enc = encodings.get(&b).ok_or(EncoderError::UnknownSymbol)?;
+                bit_writer.write_bits(enc.val, enc.num_bits)?;
+            }
+        }
+        bit_writer.finish()?;
 
-        encode_string(&input_content);
+        Ok(())
     }
 
-    pub fn decode(input_path: &String, output_path: &String) {
-        todo!();
+    /// Streaming counterpart of `decode_byte_content`: reads the (bounded)
+    /// header up front, then pulls the payload from `reader` one byte at a
+    /// time through a `BitReader`, writing each decoded byte to `writer` as
+    /// soon as its code is recognized rather than collecting a `Vec<u8>`.
+    pub fn decode_byte_stream<R: Read, W: Write>(
+        mut reader: R,
+        mut writer: W,
+    ) -> Result<(), EncoderError> {
+        let mut bitmap = [0u8; 32];
+        reader.read_exact(&mut bitmap)?;
+
+        let mut lengths = HashMap::new();
+        for b in 0u16..256 {
+            if (bitmap[b as usize / 8] >> (7 - b as usize % 8)) & 1 == 1 {
+                let mut len = [0u8; 1];
+                reader.read_exact(&mut len)?;
+                lengths.insert(b as u8, len[0]);
+            }
+        }
+
+        let mut padding_byte = [0u8; 1];
+        reader.read_exact(&mut padding_byte)?;
+        if padding_byte[0] > 7 {
+            return Err(EncoderError::MalformedHeader);
+        }
+
+        if lengths.is_empty() {
+            return Ok(());
+        }
+
+        let encodings = assign_canonical_codes(&lengths)?;
+        let mut by_code: HashMap<(u8, u64), u8> = HashMap::new();
+        for (sym, enc) in &encodings {
+            by_code.insert((enc.num_bits, enc.val), *sym);
+        }
+
+        let mut bit_reader = BitReader::new(reader)?;
+        let mut code: u64 = 0;
+        let mut num_bits: u8 = 0;
+
+        while let Some(bit) = bit_reader.next_bit(padding_byte[0])? {
+            code = (code << 1) | bit as u64;
+            num_bits += 1;
+
+            if let Some(sym) = by_code.get(&(num_bits, code)) {
+                writer.write_all(&[*sym])?;
+                code = 0;
+                num_bits = 0;
+            }
+        }
+
+        if num_bits != 0 {
+            return Err(EncoderError::TruncatedStream);
+        }
+
+        Ok(())
     }
-    fn read_file(file_path: &String) -> String {
-        fs::read_to_string(file_path).expect("Should have been able to read the file")
+
+    pub fn encode(
+        input_path: &String,
+        output_path: &String,
+        bytes_mode: bool,
+    ) -> Result<(), EncoderError> {
+        let output_file = File::create(output_path)?;
+        let mut writer = BufWriter::new(output_file);
+
+        if bytes_mode {
+            let reader = BufReader::new(File::open(input_path)?);
+            encode_byte_stream(reader, &mut writer)?;
+        } else {
+            let input_content = read_file(input_path)?;
+            writer.write_all(&encode_string(&input_content)?)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn decode(input_path: &String, output_path: &String) -> Result<(), EncoderError> {
+        let mut reader = BufReader::new(File::open(input_path)?);
+        let output_file = File::create(output_path)?;
+        let mut writer = BufWriter::new(output_file);
+
+        let mut mode = [0u8; 1];
+        reader.read_exact(&mut mode)?;
+
+        match mode[0] {
+            MODE_BYTES => decode_byte_stream(reader, &mut writer)?,
+            MODE_TEXT => {
+                let mut rest = Vec::new();
+                reader.read_to_end(&mut rest)?;
+                writer.write_all(decode_text_content(&rest)?.as_bytes())?;
+            }
+            _ => return Err(EncoderError::MalformedHeader),
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn read_file(file_path: &String) -> Result<String, EncoderError> {
+        Ok(fs::read_to_string(file_path)?)
     }
 
     #[cfg(test)]
     mod tests {
 
         use super::*;
+        use std::fs;
+
+        fn roundtrip(content: &str) -> String {
+            let packed = encode_string(&content.to_string()).unwrap();
+            decode_text_content(&packed[1..]).unwrap()
+        }
+
+        fn roundtrip_bytes(content: &[u8]) -> Vec<u8> {
+            let packed = encode_byte_content(content).unwrap();
+            decode_byte_content(&packed[1..]).unwrap()
+        }
 
         #[test]
-        fn test_encode_decode() {}
+        fn test_encode_decode_roundtrip() {
+            assert_eq!(roundtrip("hello world"), "hello world");
+            assert_eq!(
+                roundtrip("the quick brown fox jumps over the lazy dog"),
+                "the quick brown fox jumps over the lazy dog"
+            );
+            assert_eq!(roundtrip(""), "");
+            assert_eq!(roundtrip("aaaaaaaaaa"), "aaaaaaaaaa");
+            assert_eq!(roundtrip("a"), "a");
+        }
+
+        #[test]
+        fn test_encode_decode_bytes_roundtrip() {
+            assert_eq!(
+                roundtrip_bytes(&[0u8, 1, 2, 255, 254, 0, 1]),
+                vec![0u8, 1, 2, 255, 254, 0, 1]
+            );
+            assert_eq!(roundtrip_bytes(&[]), Vec::<u8>::new());
+            assert_eq!(roundtrip_bytes(&[7u8; 10]), vec![7u8; 10]);
+            assert_eq!(roundtrip_bytes(&[7u8]), vec![7u8]);
+        }
+
+        fn roundtrip_byte_stream(content: &[u8]) -> Vec<u8> {
+            use std::io::Cursor;
+
+            let mut packed = Vec::new();
+            encode_byte_stream(Cursor::new(content.to_vec()), &mut packed).unwrap();
+
+            let mut decoded = Vec::new();
+            decode_byte_stream(Cursor::new(&packed[1..]), &mut decoded).unwrap();
+            decoded
+        }
+
+        #[test]
+        fn test_encode_decode_byte_stream_roundtrip() {
+            assert_eq!(
+                roundtrip_byte_stream(&[0u8, 1, 2, 255, 254, 0, 1]),
+                vec![0u8, 1, 2, 255, 254, 0, 1]
+            );
+            assert_eq!(roundtrip_byte_stream(&[]), Vec::<u8>::new());
+            assert_eq!(roundtrip_byte_stream(&[7u8; 10]), vec![7u8; 10]);
+            assert_eq!(roundtrip_byte_stream(&[7u8]), vec![7u8]);
+
+            let large: Vec<u8> = (0..50_000u32).map(|i| (i % 251) as u8).collect();
+            assert_eq!(roundtrip_byte_stream(&large), large);
+        }
+
+        #[test]
+        fn test_canonical_codes_are_prefix_free_and_length_sorted() {
+            let encodings = build_canonical_encoding(&"aaaaaaaabbbbccd".to_string()).unwrap();
+
+            let mut by_length: Vec<(char, Encoding)> =
+                encodings.iter().map(|(c, e)| (*c, *e)).collect();
+            by_length.sort_by_key(|(c, e)| (e.num_bits, *c));
+
+            // Canonical codes increase in value within a length, and each
+            // time the length grows the running code shifts left - both
+            // requirements of the canonical assignment rule.
+            let mut prev: Option<(u8, u64)> = None;
+            for (_, enc) in &by_length {
+                if let Some((prev_len, prev_val)) = prev {
+                    assert!(enc.num_bits >= prev_len);
+                    if enc.num_bits == prev_len {
+                        assert!(enc.val > prev_val);
+                    }
+                    assert_eq!(enc.val, (prev_val + 1) << (enc.num_bits - prev_len));
+                }
+                prev = Some((enc.num_bits, enc.val));
+            }
+
+            // No shorter code is a prefix of a longer one.
+            for (_, short) in &by_length {
+                for (_, long) in &by_length {
+                    if short.num_bits < long.num_bits {
+                        assert_ne!(long.val >> (long.num_bits - short.num_bits), short.val);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_canonical_byte_codes_are_prefix_free_and_length_sorted() {
+            let encodings =
+                build_canonical_byte_encoding(&[1u8, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 4])
+                    .unwrap();
+
+            let mut by_length: Vec<(u8, Encoding)> =
+                encodings.iter().map(|(b, e)| (*b, *e)).collect();
+            by_length.sort_by_key(|(b, e)| (e.num_bits, *b));
+
+            // Canonical codes increase in value within a length, and each
+            // time the length grows the running code shifts left - both
+            // requirements of the canonical assignment rule.
+            let mut prev: Option<(u8, u64)> = None;
+            for (_, enc) in &by_length {
+                if let Some((prev_len, prev_val)) = prev {
+                    assert!(enc.num_bits >= prev_len);
+                    if enc.num_bits == prev_len {
+                        assert!(enc.val > prev_val);
+                    }
+                    assert_eq!(enc.val, (prev_val + 1) << (enc.num_bits - prev_len));
+                }
+                prev = Some((enc.num_bits, enc.val));
+            }
+
+            // No shorter code is a prefix of a longer one.
+            for (_, short) in &by_length {
+                for (_, long) in &by_length {
+                    if short.num_bits < long.num_bits {
+                        assert_ne!(long.val >> (long.num_bits - short.num_bits), short.val);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_encode_decode_files() {
+            let input_path = "test_encode_decode_files_input.txt".to_string();
+            let encoded_path = "test_encode_decode_files_encoded.bin".to_string();
+            let decoded_path = "test_encode_decode_files_decoded.txt".to_string();
+
+            fs::write(&input_path, "hello world, hello huffman!").unwrap();
+
+            encode(&input_path, &encoded_path, false).unwrap();
+            decode(&encoded_path, &decoded_path).unwrap();
+
+            let decoded = fs::read_to_string(&decoded_path).unwrap();
+            assert_eq!(decoded, "hello world, hello huffman!");
+
+            fs::remove_file(&input_path).unwrap();
+            fs::remove_file(&encoded_path).unwrap();
+            fs::remove_file(&decoded_path).unwrap();
+        }
+
+        #[test]
+        fn test_encode_decode_files_bytes_mode() {
+            let input_path = "test_encode_decode_files_bytes_input.bin".to_string();
+            let encoded_path = "test_encode_decode_files_bytes_encoded.bin".to_string();
+            let decoded_path = "test_encode_decode_files_bytes_decoded.bin".to_string();
+
+            fs::write(&input_path, [0u8, 10, 20, 255, 255, 255, 3]).unwrap();
+
+            encode(&input_path, &encoded_path, true).unwrap();
+            decode(&encoded_path, &decoded_path).unwrap();
+
+            let decoded = fs::read(&decoded_path).unwrap();
+            assert_eq!(decoded, vec![0u8, 10, 20, 255, 255, 255, 3]);
+
+            fs::remove_file(&input_path).unwrap();
+            fs::remove_file(&encoded_path).unwrap();
+            fs::remove_file(&decoded_path).unwrap();
+        }
     }
 }
@@ -1,4 +1,5 @@
 use std::env;
+mod error;
 mod huffman;
 use std::process;
 mod rustman_encoder_helper {
@@ -6,6 +7,7 @@ mod rustman_encoder_helper {
         pub action: String,
         pub input_path: String,
         pub output_path: String,
+        pub bytes: bool,
     }
 
     impl Config {
@@ -26,10 +28,16 @@ mod rustman_encoder_helper {
                 None => return Err("No output file path supplied"),
             };
 
+            // An optional trailing `--bytes` flag switches the encoder to
+            // byte mode, for compressing arbitrary binary files rather than
+            // UTF-8 text.
+            let bytes = matches!(args.next(), Some(flag) if flag == "--bytes");
+
             Ok(Config {
                 action,
                 input_path,
                 output_path,
+                bytes,
             })
         }
     }
@@ -44,9 +52,17 @@ pub fn main() {
         process::exit(1);
     });
 
-    match config.action.as_str() {
-        "encode" => encode(&config.input_path, &config.output_path),
+    let result = match config.action.as_str() {
+        "encode" => encode(&config.input_path, &config.output_path, config.bytes),
         "decode" => decode(&config.input_path, &config.output_path),
-        _ => panic!("Invalid action - please choose encode/decode"),
+        _ => {
+            eprintln!("Invalid action - please choose encode/decode");
+            process::exit(1);
+        }
     };
+
+    if let Err(err) = result {
+        eprintln!("{err}");
+        process::exit(1);
+    }
 }